@@ -1,9 +1,21 @@
-use super::context::ChartContext;
+use super::context::{ChartContext, DualCoordChartContext};
 
-use crate::coord::{AsRangedCoord, RangedCoord, Shift};
+use crate::coord::{AsRangedCoord, Ranged, RangedCoord, Shift};
 use crate::drawing::backend::DrawingBackend;
 use crate::drawing::{DrawingArea, DrawingAreaErrorKind};
-use crate::style::TextStyle;
+use crate::style::text_anchor::{HPos, Pos, VPos};
+use crate::style::{FontTransform, IntoFont, TextStyle};
+
+/// Render a tick value the same way the mesh renders its axis labels, so measured label
+/// areas (`*_label_area_size_auto`) match what actually gets drawn.
+fn format_key_point<T: std::fmt::Display>(value: &T) -> String {
+    format!("{}", value)
+}
+
+/// An upper bound on the number of key points we sample when auto-sizing a label area. This
+/// doesn't need to match the number of ticks the mesh actually draws — it just needs to be
+/// large enough that the longest/tallest label in that set is a reasonable sizing estimate.
+const AUTO_SIZE_SAMPLE_KEY_POINTS: usize = 30;
 
 /// The enum used to specify the position of label area.
 /// This is used when we configure the label area size with the API `set_label_area_size`
@@ -14,15 +26,82 @@ pub enum LabelAreaPosition {
     Right = 3,
 }
 
+/// A size used by `ChartBuilder`, either an absolute number of pixels or a fraction of
+/// the parent drawing area's corresponding dimension. This lets margins and label areas
+/// stay in proportion across backends of different sizes and DPIs. The caption is not sized
+/// by a `SizeDesc`: its size comes from the `TextStyle` passed to `caption`.
+#[derive(Clone, Copy, Debug)]
+pub enum SizeDesc {
+    /// An absolute size, in pixels
+    Pixel(i32),
+    /// A fraction of the parent drawing area's width or height, e.g. `0.1` is 10%
+    Relative(f64),
+    /// Size the label area automatically by measuring the tick labels it will contain.
+    /// Only meaningful for `x_label_area_size`/`y_label_area_size` and friends; `build_ranged`
+    /// resolves this before the breakpoint math runs.
+    Auto,
+}
+
+impl SizeDesc {
+    /// Resolve this size description into an absolute number of pixels
+    /// - `parent_dim`: The size, in pixels, of the parent drawing area along the axis
+    ///   this size is measured on
+    /// - Returns: The resolved size, in pixels
+    pub fn in_pixels(&self, parent_dim: u32) -> i32 {
+        match self {
+            SizeDesc::Pixel(size) => *size,
+            SizeDesc::Relative(ratio) => (f64::from(parent_dim) * ratio).round() as i32,
+            // `Auto` is resolved by `ChartBuilder::build_ranged` itself, which can measure
+            // tick text against the backend; callers that only have a `SizeDesc` in hand
+            // fall back to 0 rather than silently reserving space they can't size.
+            SizeDesc::Auto => 0,
+        }
+    }
+}
+
+impl From<i32> for SizeDesc {
+    fn from(size: i32) -> SizeDesc {
+        SizeDesc::Pixel(size)
+    }
+}
+
+impl From<u32> for SizeDesc {
+    fn from(size: u32) -> SizeDesc {
+        SizeDesc::Pixel(size as i32)
+    }
+}
+
+impl From<f64> for SizeDesc {
+    fn from(ratio: f64) -> SizeDesc {
+        SizeDesc::Relative(ratio)
+    }
+}
+
+impl<'a> From<&'a str> for SizeDesc {
+    /// Parse a percentage string such as `"15%"` into a `Relative` size, or a plain
+    /// integer string such as `"50"` into a `Pixel` size
+    fn from(desc: &'a str) -> SizeDesc {
+        match desc.strip_suffix('%') {
+            Some(percent) => {
+                SizeDesc::Relative(percent.trim().parse::<f64>().unwrap_or(0.0) / 100.0)
+            }
+            None => SizeDesc::Pixel(desc.trim().parse::<i32>().unwrap_or(0)),
+        }
+    }
+}
+
 /// The helper object to create a chart context, which is used for the high-level figure drawing.
 /// With the hlep of this object, we can convert a basic drawing area into a chart context, which
 /// allows the high-level chartting API beening used on the drawing area.
 pub struct ChartBuilder<'a, 'b, DB: DrawingBackend> {
-    label_area_size: [i32; 4], // [upper, lower, left, right]
+    label_area_size: [SizeDesc; 4], // [upper, lower, left, right]
     label_area_inset: [bool; 4],
     root_area: &'a DrawingArea<DB, Shift>,
     title: Option<(String, TextStyle<'b>)>,
-    margin: [u32; 4],
+    margin: [SizeDesc; 4],
+    axis_desc: [Option<String>; 4], // [upper, lower, left, right]
+    axis_desc_style: Option<TextStyle<'b>>,
+    label_style: Option<TextStyle<'b>>,
 }
 
 impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
@@ -31,53 +110,58 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
     /// - Returns: The chart builder object
     pub fn on(root: &'a DrawingArea<DB, Shift>) -> Self {
         Self {
-            label_area_size: [0; 4],
+            label_area_size: [SizeDesc::Pixel(0); 4],
             label_area_inset: [false; 4],
             root_area: root,
             title: None,
-            margin: [0; 4],
+            margin: [SizeDesc::Pixel(0); 4],
+            axis_desc: [None, None, None, None],
+            axis_desc_style: None,
+            label_style: None,
         }
     }
 
     /// Set the margin size of the chart (applied for top, bottom, left and right at the same time)
-    /// - `size`: The size of the chart margin.
-    pub fn margin(&mut self, size: u32) -> &mut Self {
+    /// - `size`: The size of the chart margin, either a pixel amount or a `"NN%"` string
+    pub fn margin<S: Into<SizeDesc>>(&mut self, size: S) -> &mut Self {
+        let size = size.into();
         self.margin = [size, size, size, size];
         self
     }
 
     /// Set the top margin of current chart
-    /// - `size`: The size of the top margin.
-    pub fn margin_top(&mut self, size: u32) -> &mut Self {
-        self.margin[0] = size;
+    /// - `size`: The size of the top margin, either a pixel amount or a `"NN%"` string
+    pub fn margin_top<S: Into<SizeDesc>>(&mut self, size: S) -> &mut Self {
+        self.margin[0] = size.into();
         self
     }
 
     /// Set the bottom margin of current chart
-    /// - `size`: The size of the bottom margin.
-    pub fn margin_bottom(&mut self, size: u32) -> &mut Self {
-        self.margin[1] = size;
+    /// - `size`: The size of the bottom margin, either a pixel amount or a `"NN%"` string
+    pub fn margin_bottom<S: Into<SizeDesc>>(&mut self, size: S) -> &mut Self {
+        self.margin[1] = size.into();
         self
     }
 
     /// Set the left margin of current chart
-    /// - `size`: The size of the left margin.
-    pub fn margin_left(&mut self, size: u32) -> &mut Self {
-        self.margin[2] = size;
+    /// - `size`: The size of the left margin, either a pixel amount or a `"NN%"` string
+    pub fn margin_left<S: Into<SizeDesc>>(&mut self, size: S) -> &mut Self {
+        self.margin[2] = size.into();
         self
     }
 
     /// Set the right margin of current chart
-    /// - `size`: The size of the right margin.
-    pub fn margin_right(&mut self, size: u32) -> &mut Self {
-        self.margin[3] = size;
+    /// - `size`: The size of the right margin, either a pixel amount or a `"NN%"` string
+    pub fn margin_right<S: Into<SizeDesc>>(&mut self, size: S) -> &mut Self {
+        self.margin[3] = size.into();
         self
     }
 
     /// Set the size of X label area
-    /// - `size`: The height of the x label area, if x is 0, the chart doesn't have the X label area
-    pub fn x_label_area_size(&mut self, size: i32) -> &mut Self {
-        self.label_area_size[1] = size;
+    /// - `size`: The height of the x label area, either a pixel amount or a `"NN%"` string.
+    ///   If size is 0, the chart doesn't have the X label area
+    pub fn x_label_area_size<S: Into<SizeDesc>>(&mut self, size: S) -> &mut Self {
+        self.label_area_size[1] = size.into();
         self
     }
 
@@ -87,9 +171,10 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
     }
 
     /// Set the size of the Y label area
-    /// - `size`: The width of the Y label area. If size is 0, the chart doesn't have Y label area
-    pub fn y_label_area_size(&mut self, size: i32) -> &mut Self {
-        self.label_area_size[2] = size;
+    /// - `size`: The width of the Y label area, either a pixel amount or a `"NN%"` string.
+    ///   If size is 0, the chart doesn't have Y label area
+    pub fn y_label_area_size<S: Into<SizeDesc>>(&mut self, size: S) -> &mut Self {
+        self.label_area_size[2] = size.into();
         self
     }
 
@@ -99,9 +184,10 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
     }
 
     /// Set the size of X label area on the top of the chart
-    /// - `size`: The height of the x label area, if x is 0, the chart doesn't have the X label area
-    pub fn top_x_label_area_size(&mut self, size: i32) -> &mut Self {
-        self.label_area_size[0] = size;
+    /// - `size`: The height of the x label area, either a pixel amount or a `"NN%"` string.
+    ///   If size is 0, the chart doesn't have the X label area
+    pub fn top_x_label_area_size<S: Into<SizeDesc>>(&mut self, size: S) -> &mut Self {
+        self.label_area_size[0] = size.into();
         self
     }
 
@@ -111,9 +197,10 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
     }
 
     /// Set the size of the Y label area on the right side
-    /// - `size`: The width of the Y label area. If size is 0, the chart doesn't have Y label area
-    pub fn right_y_label_area_size(&mut self, size: i32) -> &mut Self {
-        self.label_area_size[3] = size;
+    /// - `size`: The width of the Y label area, either a pixel amount or a `"NN%"` string.
+    ///   If size is 0, the chart doesn't have Y label area
+    pub fn right_y_label_area_size<S: Into<SizeDesc>>(&mut self, size: S) -> &mut Self {
+        self.label_area_size[3] = size.into();
         self
     }
 
@@ -124,9 +211,44 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
 
     /// Set a label area size
     /// - `pos`: THe position where the label area locted
-    /// - `size`: The size of the label area size
-    pub fn set_label_area_size(&mut self, pos: LabelAreaPosition, size: i32) -> &mut Self {
-        self.label_area_size[pos as usize] = size;
+    /// - `size`: The size of the label area size, either a pixel amount or a `"NN%"` string
+    pub fn set_label_area_size<S: Into<SizeDesc>>(&mut self, pos: LabelAreaPosition, size: S) -> &mut Self {
+        self.label_area_size[pos as usize] = size.into();
+        self
+    }
+
+    /// Automatically size the bottom X label area by measuring the tick labels it will hold
+    pub fn x_label_area_size_auto(&mut self) -> &mut Self {
+        self.label_area_size[1] = SizeDesc::Auto;
+        self
+    }
+
+    /// Automatically size the left Y label area by measuring the tick labels it will hold
+    pub fn y_label_area_size_auto(&mut self) -> &mut Self {
+        self.label_area_size[2] = SizeDesc::Auto;
+        self
+    }
+
+    /// Automatically size the top X label area by measuring the tick labels it will hold
+    pub fn top_x_label_area_size_auto(&mut self) -> &mut Self {
+        self.label_area_size[0] = SizeDesc::Auto;
+        self
+    }
+
+    /// Automatically size the right Y label area by measuring the primary Y axis's tick labels.
+    /// With `build_ranged_with_secondary` the secondary axis's labels are not measured.
+    pub fn right_y_label_area_size_auto(&mut self) -> &mut Self {
+        self.label_area_size[3] = SizeDesc::Auto;
+        self
+    }
+
+    /// Set the text style used to measure tick labels for `*_label_area_size_auto` and to draw
+    /// the secondary axis's tick labels from `build_ranged_with_secondary`. It is not passed on
+    /// to the `ChartContext` that `build_ranged` returns. Defaults to a 12pt sans-serif font
+    /// when unset.
+    /// - `style`: The text style
+    pub fn label_style<Style: Into<TextStyle<'b>>>(&mut self, style: Style) -> &mut Self {
+        self.label_style = Some(style.into());
         self
     }
 
@@ -143,31 +265,80 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
         self
     }
 
+    /// Set the description of the bottom X axis, drawn centered along the axis at the outer
+    /// edge of its label area (which is enlarged to fit it), the way `caption` titles the
+    /// whole chart
+    /// - `desc`: The axis description
+    pub fn x_desc<S: Into<String>>(&mut self, desc: S) -> &mut Self {
+        self.axis_desc[1] = Some(desc.into());
+        self
+    }
+
+    /// Set the description of the left Y axis
+    /// - `desc`: The axis description
+    pub fn y_desc<S: Into<String>>(&mut self, desc: S) -> &mut Self {
+        self.axis_desc[2] = Some(desc.into());
+        self
+    }
+
+    /// Set the description of the top X axis
+    /// - `desc`: The axis description
+    pub fn top_x_desc<S: Into<String>>(&mut self, desc: S) -> &mut Self {
+        self.axis_desc[0] = Some(desc.into());
+        self
+    }
+
+    /// Set the description of the right Y axis
+    /// - `desc`: The axis description
+    pub fn right_y_desc<S: Into<String>>(&mut self, desc: S) -> &mut Self {
+        self.axis_desc[3] = Some(desc.into());
+        self
+    }
+
+    /// Set the text style used to draw the axis descriptions set via `x_desc`/`y_desc` and
+    /// their top/right variants
+    /// - `style`: The text style
+    pub fn axis_desc_style<Style: Into<TextStyle<'b>>>(&mut self, style: Style) -> &mut Self {
+        self.axis_desc_style = Some(style.into());
+        self
+    }
+
     /// Build the chart with a 2D Cartesian coordinate system. The function will returns a chart
     /// context, where data series can be rendered on.
     /// - `x_spec`: The specification of X axis
     /// - `y_spec`: The specification of Y axis
     /// - Returns: A chart context
     #[allow(clippy::type_complexity)]
-    pub fn build_ranged<X: AsRangedCoord, Y: AsRangedCoord>(
+    pub fn build_ranged<X, Y>(
         &mut self,
         x_spec: X,
         y_spec: Y,
     ) -> Result<
         ChartContext<'a, DB, RangedCoord<X::CoordDescType, Y::CoordDescType>>,
         DrawingAreaErrorKind<DB::ErrorType>,
-    > {
+    >
+    where
+        X: AsRangedCoord + Clone,
+        Y: AsRangedCoord + Clone,
+        X::CoordDescType: Ranged,
+        Y::CoordDescType: Ranged,
+        <X::CoordDescType as Ranged>::ValueType: std::fmt::Display,
+        <Y::CoordDescType as Ranged>::ValueType: std::fmt::Display,
+    {
         let mut label_areas = [None, None, None, None];
 
         let mut drawing_area = DrawingArea::clone(self.root_area);
 
-        if *self.margin.iter().max().unwrap_or(&0) > 0 {
-            drawing_area = drawing_area.margin(
-                self.margin[0] as i32,
-                self.margin[1] as i32,
-                self.margin[2] as i32,
-                self.margin[3] as i32,
-            );
+        let (root_w, root_h) = drawing_area.dim_in_pixel();
+        let margin = [
+            self.margin[0].in_pixels(root_h),
+            self.margin[1].in_pixels(root_h),
+            self.margin[2].in_pixels(root_w),
+            self.margin[3].in_pixels(root_w),
+        ];
+
+        if *margin.iter().max().unwrap_or(&0) > 0 {
+            drawing_area = drawing_area.margin(margin[0], margin[1], margin[2], margin[3]);
         }
 
         if let Some((ref title, ref style)) = self.title {
@@ -176,11 +347,101 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
 
         let (w, h) = drawing_area.dim_in_pixel();
 
+        let mut label_area_size = [
+            self.label_area_size[0].in_pixels(root_h),
+            self.label_area_size[1].in_pixels(root_h),
+            self.label_area_size[2].in_pixels(root_w),
+            self.label_area_size[3].in_pixels(root_w),
+        ];
+
+        if self
+            .label_area_size
+            .iter()
+            .any(|size| matches!(size, SizeDesc::Auto))
+        {
+            // The axis ranges must exist before we can enumerate their ticks, so build them
+            // from the specs (bound to no pixel area yet), measure the formatted tick labels
+            // against the backend, and replace the `Auto` entries of the sizes computed above.
+            let label_style = self
+                .label_style
+                .clone()
+                .unwrap_or_else(|| TextStyle::from(("sans-serif", 12).into_font()));
+            let x_coord = x_spec.clone().into_ranged();
+            let y_coord = y_spec.clone().into_ranged();
+
+            let measure = |text: &str| {
+                drawing_area
+                    .estimate_text_size(text, &label_style)
+                    .unwrap_or((0, 0))
+            };
+
+            const LABEL_AREA_PADDING: i32 = 5;
+
+            if matches!(self.label_area_size[0], SizeDesc::Auto) {
+                label_area_size[0] = x_coord
+                    .key_points(AUTO_SIZE_SAMPLE_KEY_POINTS)
+                    .iter()
+                    .map(|v| measure(&format_key_point(v)).1 as i32)
+                    .max()
+                    .unwrap_or(0)
+                    + LABEL_AREA_PADDING;
+            }
+
+            if matches!(self.label_area_size[1], SizeDesc::Auto) {
+                label_area_size[1] = x_coord
+                    .key_points(AUTO_SIZE_SAMPLE_KEY_POINTS)
+                    .iter()
+                    .map(|v| measure(&format_key_point(v)).1 as i32)
+                    .max()
+                    .unwrap_or(0)
+                    + LABEL_AREA_PADDING;
+            }
+
+            if matches!(self.label_area_size[2], SizeDesc::Auto) {
+                label_area_size[2] = y_coord
+                    .key_points(AUTO_SIZE_SAMPLE_KEY_POINTS)
+                    .iter()
+                    .map(|v| measure(&format_key_point(v)).0 as i32)
+                    .max()
+                    .unwrap_or(0)
+                    + LABEL_AREA_PADDING;
+            }
+
+            if matches!(self.label_area_size[3], SizeDesc::Auto) {
+                label_area_size[3] = y_coord
+                    .key_points(AUTO_SIZE_SAMPLE_KEY_POINTS)
+                    .iter()
+                    .map(|v| measure(&format_key_point(v)).0 as i32)
+                    .max()
+                    .unwrap_or(0)
+                    + LABEL_AREA_PADDING;
+            }
+        }
+
+        let desc_style = self
+            .axis_desc_style
+            .clone()
+            .unwrap_or_else(|| TextStyle::from(("sans-serif", 12).into_font()))
+            .pos(Pos::new(HPos::Center, VPos::Top));
+
+        const AXIS_DESC_PADDING: i32 = 5;
+
+        for (idx, desc) in self.axis_desc.iter().enumerate() {
+            if let Some(desc) = desc {
+                // The Y-axis descriptions are drawn rotated 90 degrees (see below), so their
+                // footprint in the label area is always the text's unrotated *height*, whether
+                // it ends up reserving X-axis height (idx 0/1) or Y-axis width (idx 2/3).
+                let (_, dh) = drawing_area
+                    .estimate_text_size(desc, &desc_style)
+                    .unwrap_or((0, 0));
+                label_area_size[idx] += dh as i32 + AXIS_DESC_PADDING;
+            }
+        }
+
         let mut actual_drawing_area_pos = [0, h as i32, 0, w as i32];
 
         for (idx, (dx, dy)) in (0..4).map(|idx| (idx, [(0, -1), (0, 1), (-1, 0), (1, 0)][idx])) {
-            //let size = if self.label_area_size[idx] <= 0 { 0 } else { self.label_area_size[idx] };
-            let size = self.label_area_size[idx];
+            let size = label_area_size[idx];
             let split_point = if !self.label_area_inset[idx] {
                 if dx + dy < 0 {
                     size
@@ -212,7 +473,7 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
         for (id, (_, size)) in self
             .label_area_inset
             .iter()
-            .zip(self.label_area_size.iter())
+            .zip(label_area_size.iter())
             .enumerate()
             .filter(|(_, (inset, size))| **inset && **size != 0)
         {
@@ -234,6 +495,31 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
             std::mem::swap(&mut label_areas[id], &mut Some(new_area));
         }
 
+        for (idx, desc) in self.axis_desc.iter().enumerate() {
+            if let Some(desc) = desc {
+                if let Some(area) = label_areas[idx].as_ref() {
+                    let (aw, ah) = area.dim_in_pixel();
+                    if idx < 2 {
+                        let y = if idx == 0 {
+                            AXIS_DESC_PADDING
+                        } else {
+                            let (_, dh) = area.estimate_text_size(desc, &desc_style).unwrap_or((0, 0));
+                            ah as i32 - dh as i32 - AXIS_DESC_PADDING
+                        };
+                        area.draw_text(desc, &desc_style, (aw as i32 / 2, y))?;
+                    } else {
+                        let x = if idx == 2 {
+                            AXIS_DESC_PADDING
+                        } else {
+                            aw as i32 - AXIS_DESC_PADDING
+                        };
+                        let rotated_style = desc_style.clone().transform(FontTransform::Rotate90);
+                        area.draw_text(desc, &rotated_style, (x, ah as i32 / 2))?;
+                    }
+                }
+            }
+        }
+
         std::mem::swap(&mut drawing_area, &mut splitted[4].as_mut().unwrap());
 
         let mut pixel_range = drawing_area.get_pixel_range();
@@ -258,4 +544,47 @@ impl<'a, 'b, DB: DrawingBackend> ChartBuilder<'a, 'b, DB> {
             series_anno: vec![],
         })
     }
+
+    /// Build the chart with a primary 2D Cartesian coordinate system and a secondary
+    /// coordinate system sharing the same X axis but with its own Y range. The secondary
+    /// coordinate is rendered into the right-hand Y label area reserved by
+    /// `right_y_label_area_size`, independently from the primary left/bottom axes.
+    /// - `x_spec`: The specification of the (shared) X axis
+    /// - `y_spec`: The specification of the primary (left) Y axis
+    /// - `secondary_y_spec`: The specification of the secondary (right) Y axis
+    /// - Returns: A dual-coordinate chart context
+    #[allow(clippy::type_complexity)]
+    pub fn build_ranged_with_secondary<X, Y, Y2>(
+        &mut self,
+        x_spec: X,
+        y_spec: Y,
+        secondary_y_spec: Y2,
+    ) -> Result<
+        DualCoordChartContext<
+            'a,
+            DB,
+            RangedCoord<X::CoordDescType, Y::CoordDescType>,
+            RangedCoord<X::CoordDescType, Y2::CoordDescType>,
+            Y2::CoordDescType,
+        >,
+        DrawingAreaErrorKind<DB::ErrorType>,
+    >
+    where
+        X: AsRangedCoord + Clone,
+        Y: AsRangedCoord + Clone,
+        Y2: AsRangedCoord + Clone,
+        X::CoordDescType: Ranged,
+        Y::CoordDescType: Ranged,
+        Y2::CoordDescType: Ranged + Clone,
+        <X::CoordDescType as Ranged>::ValueType: std::fmt::Display,
+        <Y::CoordDescType as Ranged>::ValueType: std::fmt::Display,
+    {
+        let label_style = self
+            .label_style
+            .clone()
+            .unwrap_or_else(|| TextStyle::from(("sans-serif", 12).into_font()));
+
+        let primary = self.build_ranged(x_spec.clone(), y_spec)?;
+        Ok(primary.set_secondary_coord(x_spec, secondary_y_spec, label_style))
+    }
 }
@@ -0,0 +1,130 @@
+use std::borrow::Borrow;
+use std::ops::{Deref, DerefMut};
+
+use crate::coord::{AsRangedCoord, RangedCoord, Ranged, Shift};
+use crate::drawing::backend::DrawingBackend;
+use crate::drawing::{DrawingArea, DrawingAreaErrorKind};
+use crate::element::Drawable;
+use crate::style::TextStyle;
+
+/// The context of the chart. This is what `ChartBuilder::build_ranged` hands back: the
+/// label areas carved out by the builder plus the drawing area bound to the coordinate
+/// system data series are drawn into.
+pub struct ChartContext<'a, DB: DrawingBackend, CT> {
+    pub(super) x_label_area: [Option<DrawingArea<DB, Shift>>; 2],
+    pub(super) y_label_area: [Option<DrawingArea<DB, Shift>>; 2],
+    pub(super) drawing_area: DrawingArea<DB, CT>,
+    pub(super) series_anno: Vec<()>,
+}
+
+impl<'a, DB: DrawingBackend, CT> ChartContext<'a, DB, CT> {
+    /// Attach an independent secondary coordinate system to this chart, sharing the same
+    /// pixel rectangle as the primary coordinate but translating points through its own X/Y
+    /// ranges. The right-hand Y label area reserved by `right_y_label_area_size` becomes the
+    /// secondary axis's tick area.
+    /// - `x_spec`: The specification of the (shared) X axis, in the secondary coordinate's terms
+    /// - `y_spec`: The specification of the secondary Y axis
+    /// - `label_style`: The text style used to draw the secondary axis's tick labels
+    /// - Returns: A dual-coordinate chart context
+    pub fn set_secondary_coord<X, Y2>(
+        self,
+        x_spec: X,
+        y_spec: Y2,
+        label_style: TextStyle<'a>,
+    ) -> DualCoordChartContext<'a, DB, CT, RangedCoord<X::CoordDescType, Y2::CoordDescType>, Y2::CoordDescType>
+    where
+        X: AsRangedCoord,
+        Y2: AsRangedCoord + Clone,
+        Y2::CoordDescType: Ranged + Clone,
+    {
+        // Match `build_ranged`: flip the Y half of the pixel range so the coordinate's
+        // minimum maps to the bottom of the drawing area, not the top.
+        let mut pixel_range = self.drawing_area.get_pixel_range();
+        pixel_range.1 = pixel_range.1.end..pixel_range.1.start;
+
+        let secondary_y_coord = y_spec.clone().into_ranged();
+        let secondary_drawing_area = self
+            .drawing_area
+            .clone()
+            .apply_coord_spec(RangedCoord::new(x_spec, y_spec, pixel_range));
+
+        DualCoordChartContext {
+            primary: self,
+            secondary_drawing_area,
+            secondary_y_coord,
+            label_style,
+        }
+    }
+}
+
+/// A chart context that holds two independent coordinate systems sharing the same pixel
+/// rectangle — the primary X/Y coordinate used by the regular `ChartContext` API, and a
+/// secondary Y coordinate rendered into the right-hand label area. Calls that aren't about
+/// the secondary axis fall through to the primary `ChartContext` via `Deref`.
+pub struct DualCoordChartContext<'a, DB: DrawingBackend, CT, CT2, YC2> {
+    pub(super) primary: ChartContext<'a, DB, CT>,
+    pub(super) secondary_drawing_area: DrawingArea<DB, CT2>,
+    pub(super) secondary_y_coord: YC2,
+    pub(super) label_style: TextStyle<'a>,
+}
+
+impl<'a, DB: DrawingBackend, CT, CT2, YC2> Deref for DualCoordChartContext<'a, DB, CT, CT2, YC2> {
+    type Target = ChartContext<'a, DB, CT>;
+    fn deref(&self) -> &Self::Target {
+        &self.primary
+    }
+}
+
+impl<'a, DB: DrawingBackend, CT, CT2, YC2> DerefMut for DualCoordChartContext<'a, DB, CT, CT2, YC2> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.primary
+    }
+}
+
+impl<'a, DB: DrawingBackend, CT, CT2, YC2: Ranged> DualCoordChartContext<'a, DB, CT, CT2, YC2>
+where
+    YC2::ValueType: std::fmt::Debug,
+{
+    /// Draw a data series against the secondary coordinate system established by
+    /// `set_secondary_coord`
+    /// - `series`: An iterator of drawable elements, interpreted in the secondary
+    ///   coordinate's space
+    pub fn draw_secondary_series<E, R, S>(
+        &mut self,
+        series: S,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+    where
+        E: Drawable<DB>,
+        R: Borrow<E>,
+        S: IntoIterator<Item = R>,
+    {
+        for element in series {
+            self.secondary_drawing_area.draw(element.borrow())?;
+        }
+        Ok(())
+    }
+
+    /// Render the secondary Y axis's tick labels into the right-hand label area reserved by
+    /// `right_y_label_area_size`. No tick marks or axis line are drawn.
+    pub fn configure_secondary_axes(&mut self) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>> {
+        let area = match self.primary.y_label_area[1].as_ref() {
+            Some(area) => area,
+            None => return Ok(()),
+        };
+
+        let (w, h) = area.dim_in_pixel();
+
+        for key_point in self.secondary_y_coord.key_points(10) {
+            // Flipped, like the main drawing area's pixel range, so the minimum lands at the
+            // bottom and ticks line up with the primary left axis.
+            let y = self.secondary_y_coord.map(&key_point, (h as i32, 0));
+            area.draw_text(
+                &format!("{:?}", key_point),
+                &self.label_style,
+                (w as i32 / 2, y),
+            )?;
+        }
+
+        Ok(())
+    }
+}